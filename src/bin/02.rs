@@ -9,8 +9,7 @@ fn is_safe(data: &[u32]) -> bool {
         return false;
     }
     data.iter().tuple_windows::<(&u32, &u32)>()
-        .map(|(current, next)| (1..=3).contains(&current.abs_diff(*next)) && current.cmp(next) == direction)
-        .all(|valid| valid)
+        .all(|(current, next)| (1..=3).contains(&current.abs_diff(*next)) && current.cmp(next) == direction)
 }
 
 fn is_safe_lenient(data: &[u32]) -> bool {
@@ -28,7 +28,7 @@ fn is_valid_equation(terms: &[u64], expected: &u64) -> bool {
 
     let x = terms.last().unwrap();
     let slice = &terms[..terms.len() - 1];
-    if expected % x == 0 && is_valid_equation(slice, &(expected / x)) {
+    if expected.is_multiple_of(*x) && is_valid_equation(slice, &(expected / x)) {
         true
     } else {
         expected >= x && is_valid_equation(slice, &(expected - x))
@@ -42,9 +42,8 @@ fn is_valid_equation_with_concat(terms: &[u64], expected: &u64) -> bool {
 
     let right = terms.last().unwrap();
     let slice = &terms[..terms.len() - 1];
-    if expected % right == 0 && is_valid_equation_with_concat(slice, &(expected / right)) {
-        true
-    } else if expected >= right && is_valid_equation_with_concat(slice, &(expected - right)) {
+    if (expected.is_multiple_of(*right) && is_valid_equation_with_concat(slice, &(expected / right)))
+        || (expected >= right && is_valid_equation_with_concat(slice, &(expected - right))) {
         true
     } else {
         let n_digits = right.ilog10() + 1;
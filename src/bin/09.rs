@@ -1,3 +1,4 @@
+use advent_of_code::range_set::RangeSet;
 use std::ops::Range;
 use itertools::Itertools;
 
@@ -60,8 +61,9 @@ struct Interval {
 }
 
 impl DiskMap<Interval> {
-    fn parse_intervals(input: &str) -> DiskMap<Interval> {
+    fn parse_intervals(input: &str) -> (DiskMap<Interval>, RangeSet<u32>) {
         let mut last_index = 0;
+        let mut free_space = RangeSet::new();
         let data = (&input.chars().chunks(2)).into_iter()
             .enumerate()
             .map(|(id_number, mut chunk)| {
@@ -71,35 +73,34 @@ impl DiskMap<Interval> {
                     interval: last_index..(last_index + file_size)
                 };
                 last_index = file.interval.end;
-                if let Some(Some(free_space)) = chunk.next().map(|char| char.to_digit(10)) {
-                    last_index += free_space;
+                if let Some(Some(free_size)) = chunk.next().map(|char| char.to_digit(10)) {
+                    free_space.insert(last_index..(last_index + free_size));
+                    last_index += free_size;
                 }
                 file
             })
             .collect_vec();
-        DiskMap {
+        (DiskMap {
             data
+        }, free_space)
+    }
+
+    fn compact(&mut self, free_space: &mut RangeSet<u32>) {
+        for file in self.data.iter_mut().rev() {
+            let size = file.interval.end - file.interval.start;
+            let Some(gap) = free_space.first_fit(size) else { continue };
+            if gap.start >= file.interval.start {
+                continue;
+            }
+            let new_interval = gap.start..(gap.start + size);
+            free_space.remove(new_interval.clone());
+            free_space.insert(file.interval.clone());
+            file.interval = new_interval;
         }
     }
 
     fn checksum(&self) -> u64 {
-        let mut data = self.data.clone();
-        for index in (1..data.len()).rev() {
-            let interval = &self.data[index];
-            let actual_index = data.iter().rposition(|x| x.id == interval.id).unwrap();
-            let interval_size = interval.interval.end - interval.interval.start;
-            let Some((new_index, start_index, _)) = (0..actual_index)
-                .map(|index| (index, &data[index].interval, &data[index + 1].interval))
-                .map(|(index, x, y)| (index, x.end, y.start - x.end))
-                .find(|(_, _, size)| size >= &(interval_size)) else { continue };
-            let interval = Interval {
-                interval: start_index..(start_index + interval_size),
-                ..*interval
-            };
-            data.remove(actual_index);
-            data.insert(new_index + 1, interval);
-        }
-        data.iter()
+        self.data.iter()
             .map(|Interval { id, interval }| interval.clone().map(|x| id * (x as u64)).sum::<u64>())
             .sum()
     }
@@ -110,7 +111,8 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let disk_map = DiskMap::parse_intervals(input);
+    let (mut disk_map, mut free_space) = DiskMap::parse_intervals(input);
+    disk_map.compact(&mut free_space);
     Some(disk_map.checksum())
 }
 
@@ -1,21 +1,16 @@
+use advent_of_code::memo::Memo;
 use itertools::Itertools;
-use std::collections::HashMap;
 
 advent_of_code::solution!(11);
 
-fn blink(stone: u64, iterations_left: usize, memo: &mut HashMap<(u64, usize), u64>) -> u64 {
+fn blink(memo: &mut Memo<(u64, usize), u64>, (stone, iterations_left): (u64, usize)) -> u64 {
     if iterations_left == 0 {
         return 1;
     }
-    if let Some(cached_value) = memo.get(&(stone, iterations_left)) {
-        return *cached_value;
-    }
 
     let (first_half, second_half) = blink_stone(stone);
-    let mut sum = blink(first_half, iterations_left - 1, memo);
-    if let Some(second_half) = second_half { sum += blink(second_half, iterations_left - 1, memo); }
-
-    memo.insert((stone, iterations_left), sum);
+    let mut sum = memo.get((first_half, iterations_left - 1));
+    if let Some(second_half) = second_half { sum += memo.get((second_half, iterations_left - 1)); }
 
     sum
 }
@@ -26,7 +21,7 @@ fn blink_stone(stone: u64) -> (u64, Option<u64>) {
     }
 
     let digits = stone.ilog10() + 1;
-    if digits % 2 != 0 {
+    if !digits.is_multiple_of(2) {
         return (stone * 2024, None)
     }
     let divisor = 10u64.pow(digits / 2);
@@ -37,14 +32,14 @@ fn blink_stone(stone: u64) -> (u64, Option<u64>) {
 
 pub fn part_one(input: &str) -> Option<u64> {
     let stones = input.split_ascii_whitespace().map(|number| number.parse::<u64>().unwrap()).collect_vec();
-    let mut memo = HashMap::new();
-    Some(stones.iter().map(|stone| blink(*stone, 25, &mut memo)).sum())
+    let mut memo = Memo::new(blink);
+    Some(stones.iter().map(|stone| memo.get((*stone, 25))).sum())
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
     let stones = input.split_ascii_whitespace().map(|number| number.parse::<u64>().unwrap()).collect_vec();
-    let mut memo = HashMap::new();
-    Some(stones.iter().map(|stone| blink(*stone, 75, &mut memo)).sum())
+    let mut memo = Memo::new(blink);
+    Some(stones.iter().map(|stone| memo.get((*stone, 75))).sum())
 }
 
 #[cfg(test)]
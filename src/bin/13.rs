@@ -5,12 +5,14 @@ advent_of_code::solution!(13);
 
 const PREFIX: i64 = 10000000000000;
 
-fn parse(input: &str) -> Vec<((i64, i64, i64), (i64, i64, i64))> {
+type Equation = ((i64, i64, i64), (i64, i64, i64));
+
+fn parse(input: &str) -> Vec<Equation> {
     let pattern = Regex::new(r"\n\s*\n").unwrap();
     pattern.split(input).map(parse_equation).collect()
 }
 
-fn parse_with_prefix(input: &str) -> Vec<((i64, i64, i64), (i64, i64, i64))> {
+fn parse_with_prefix(input: &str) -> Vec<Equation> {
     let pattern = Regex::new(r"\n\s*\n").unwrap();
     pattern.split(input).map(parse_equation_with_prefix).collect()
 }
@@ -1,3 +1,7 @@
+use advent_of_code::cycle;
+use advent_of_code::grid::Grid;
+use advent_of_code::picture::{self, Scorer};
+use advent_of_code::template::visualize;
 use itertools::Itertools;
 
 advent_of_code::solution!(14);
@@ -12,7 +16,7 @@ const WIDTH: u8 = 11;
 #[cfg(test)]
 const HEIGHT: u8 = 7;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 struct Robot {
     position: (isize, isize),
     velocity: (isize, isize),
@@ -54,6 +58,11 @@ impl Robot {
     }
 }
 
+fn render(robots: &[Robot]) -> Grid<visualize::Rgb> {
+    let points = robots.iter().map(|robot| (robot.position.0 as usize, robot.position.1 as usize));
+    Grid::from_points(WIDTH as usize, HEIGHT as usize, points, visualize::GREEN, visualize::BLACK)
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
     let mut quadrants = [0; 4];
     input.lines()
@@ -68,23 +77,25 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (mut lowest_safety_factor, mut seconds_passed) = (u32::MAX, 0);
-    let mut robots = input.lines().map(Robot::parse).collect_vec();
-    for second in 1..=WIDTH as u16 * HEIGHT as u16 {
-        let mut quadrants = [0; 4];
-        robots.iter_mut()
-            .map(|robot| {
-                robot.simulate(1);
-                robot
-            })
-            .filter_map(|robot| robot.get_quadrant())
-            .for_each(|quadrant| quadrants[quadrant as usize] += 1);
-        let safety_factor = quadrants.iter().product();
-        if safety_factor < lowest_safety_factor {
-            lowest_safety_factor = safety_factor;
-            seconds_passed = second;
-        }
-    }
+    let robots = input.lines().map(Robot::parse).collect_vec();
+    let period = cycle::brent(robots.clone(), |robots| {
+        robots.iter().cloned().map(|mut robot| {
+            robot.simulate(1);
+            robot
+        }).collect_vec()
+    }).length;
+    let seconds_passed = picture::find_frame(
+        robots.clone(),
+        period,
+        |robots| robots.iter_mut().for_each(|robot| robot.simulate(1)),
+        |robots| robots.iter().map(|robot| robot.position).collect_vec(),
+        Scorer::Variance,
+    );
+    visualize::frame(|| {
+        let mut robots = robots;
+        robots.iter_mut().for_each(|robot| robot.simulate(seconds_passed as isize));
+        render(&robots)
+    });
     Some(seconds_passed as u32)
 }
 
@@ -100,6 +111,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        // Cannot test part two
+        let result = part_two(&advent_of_code::template::read_file_part("examples", DAY, 2));
+        assert_eq!(result, Some(5));
     }
 }
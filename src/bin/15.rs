@@ -1,3 +1,5 @@
+use advent_of_code::grid::Grid;
+use advent_of_code::template::visualize::{self, Rgb};
 use itertools::Itertools;
 use std::cmp::PartialEq;
 
@@ -34,11 +36,27 @@ struct Map<T> {
 trait GenericMap {
     fn parse(input: &str) -> (Self, Vec<(isize, isize)>) where Self: Sized;
     fn play(&mut self, instructions: &[(isize, isize)]) {
+        visualize::frame(|| self.render());
         for instruction in instructions {
             self.move_robot(instruction);
+            visualize::frame(|| self.render());
         }
     }
 
+    fn render(&self) -> Grid<Rgb> {
+        Grid::from_fn(self.width(), self.height(), |pos| {
+            let index = self.xy_to_index(pos).unwrap();
+            if index == self.robot() {
+                visualize::RED
+            } else {
+                self.tile_color(index)
+            }
+        })
+    }
+
+    fn robot(&self) -> usize;
+    fn tile_color(&self, index: usize) -> Rgb;
+
     fn move_robot(&mut self, direction: &(isize, isize));
     fn move_box(&mut self, pos: usize, direction: &(isize, isize)) -> bool;
 
@@ -117,11 +135,10 @@ impl GenericMap for Map<Tile> {
 
     fn move_robot(&mut self, direction: &(isize, isize)) {
         let Some(new_pos) = self.offset(&self.robot, direction) else { return };
-        match self.tiles[new_pos] {
+        let tile = self.tiles[new_pos];
+        match tile {
             Tile::Empty => self.robot = new_pos,
-            Tile::Box => if self.move_box(new_pos, direction) {
-                self.robot = new_pos;
-            }
+            Tile::Box if self.move_box(new_pos, direction) => self.robot = new_pos,
             _ => {}
         }
     }
@@ -150,6 +167,18 @@ impl GenericMap for Map<Tile> {
     fn height(&self) -> usize {
         self.height
     }
+
+    fn robot(&self) -> usize {
+        self.robot
+    }
+
+    fn tile_color(&self, index: usize) -> Rgb {
+        match self.tiles[index] {
+            Tile::Empty => visualize::BLACK,
+            Tile::Wall => visualize::GREY,
+            Tile::Box => visualize::YELLOW,
+        }
+    }
 }
 
 impl GenericMap for Map<FatTile> {
@@ -200,11 +229,10 @@ impl GenericMap for Map<FatTile> {
 
     fn move_robot(&mut self, direction: &(isize, isize)) {
         let Some(new_pos) = self.offset(&self.robot, direction) else { return };
-        match self.tiles[new_pos] {
+        let tile = self.tiles[new_pos];
+        match tile {
             FatTile::Empty => self.robot = new_pos,
-            FatTile::LeftBox | FatTile::RightBox => if self.move_box(new_pos, direction) {
-                self.robot = new_pos;
-            }
+            FatTile::LeftBox | FatTile::RightBox if self.move_box(new_pos, direction) => self.robot = new_pos,
             _ => {}
         }
     }
@@ -221,7 +249,8 @@ impl GenericMap for Map<FatTile> {
 
         let current_tile = self.tiles[pos];
         let Some(new_pos) = self.offset(&pos, direction) else { return false };
-        match self.tiles[new_pos] {
+        let tile = self.tiles[new_pos];
+        match tile {
             FatTile::Empty => {
                 self.tiles[pos] = FatTile::Empty;
                 self.tiles[new_pos] = current_tile;
@@ -246,6 +275,18 @@ impl GenericMap for Map<FatTile> {
     fn height(&self) -> usize {
         self.height
     }
+
+    fn robot(&self) -> usize {
+        self.robot
+    }
+
+    fn tile_color(&self, index: usize) -> Rgb {
+        match self.tiles[index] {
+            FatTile::Empty => visualize::BLACK,
+            FatTile::Wall => visualize::GREY,
+            FatTile::LeftBox | FatTile::RightBox => visualize::YELLOW,
+        }
+    }
 }
 
 impl Map<FatTile> {
@@ -1,141 +1,141 @@
+use advent_of_code::vm::{self, Control, InstructionSet, State, Vm, VmError};
 use itertools::Itertools;
+use std::fmt::Display;
 
 advent_of_code::solution!(17);
 
 const REGISTER_LEN: usize = "Register _: ".len();
 const PROGRAM_LEN: usize = "Program: ".len();
+const CYCLE_LIMIT: usize = 10_000;
 
 #[derive(Debug)]
 enum Instruction {
-    Adv,
-    Bxl,
-    Bst,
-    Jnz,
+    Adv(Operand),
+    Bxl(i64),
+    Bst(Operand),
+    Jnz(i64),
     Bxc,
-    Out,
-    Bdv,
-    Cdv,
+    Out(Operand),
+    Bdv(Operand),
+    Cdv(Operand),
 }
 
-impl From<u8> for Instruction {
-    fn from(value: u8) -> Self {
-        use Instruction::*;
-        match value {
-            0 => Adv,
-            1 => Bxl,
-            2 => Bst,
-            3 => Jnz,
-            4 => Bxc,
-            5 => Out,
-            6 => Bdv,
-            7 => Cdv,
-            _ => panic!(),
+#[derive(Debug)]
+enum Operand {
+    Literal(i64),
+    Register(usize),
+}
+
+impl Operand {
+    fn combo(ip: usize, operand: i64) -> Result<Operand, VmError> {
+        match operand {
+            0..=3 => Ok(Operand::Literal(operand)),
+            4..=6 => Ok(Operand::Register(operand as usize - 4)),
+            _ => Err(VmError::InvalidOperand { ip, operand }),
         }
     }
-}
 
-impl Instruction {
-    fn process(&self, computer: &mut Computer, operand: u8) -> bool {
-        use Instruction::*;
-        let literal = operand as u64;
-        let combo = || match operand {
-            0..=3 => operand as u64,
-            4 => computer.register_a,
-            5 => computer.register_b,
-            6 => computer.register_c,
-            _ => panic!(),
-        };
+    fn value(&self, state: &State) -> i64 {
         match self {
-            Adv => computer.register_a /= 2u64.pow(combo() as u32),
-            Bxl => computer.register_b ^= literal,
-            Bst => computer.register_b = combo() & 0b111,
-            Jnz => {
-                if computer.register_a == 0 {
-                    return true;
-                }
-                computer.instruction_pointer = literal as u8;
-                return false;
-            },
-            Bxc => computer.register_b ^= computer.register_c,
-            Out => computer.output.push((combo() & 0b111) as u8),
-            Bdv => computer.register_b = computer.register_a / 2u64.pow(combo() as u32),
-            Cdv => computer.register_c = computer.register_a / 2u64.pow(combo() as u32),
+            Operand::Literal(value) => *value,
+            Operand::Register(register) => state.registers[*register],
         }
-        true
     }
 }
 
-#[derive(Debug, Default)]
-struct Computer {
-    program: Vec<u8>,
-    register_a: u64,
-    register_b: u64,
-    register_c: u64,
-    instruction_pointer: u8,
-    output: Vec<u8>,
+impl Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operand::Literal(value) => write!(f, "{value}"),
+            Operand::Register(register) => f.write_str(Instruction::REGISTERS[*register]),
+        }
+    }
 }
 
-impl Computer {
-    fn parse(input: &str) -> Self {
-        let mut lines = input.lines();
-        let register_a = lines.next().and_then(|line| line[REGISTER_LEN..].parse::<u64>().ok()).unwrap_or_default();
-        let register_b = lines.next().and_then(|line| line[REGISTER_LEN..].parse::<u64>().ok()).unwrap_or_default();
-        let register_c = lines.next().and_then(|line| line[REGISTER_LEN..].parse::<u64>().ok()).unwrap_or_default();
-        lines.next();
-        let program = lines.next().iter()
-            .flat_map(|line| line[PROGRAM_LEN..].split(','))
-            .flat_map(|number| number.parse::<u8>().ok())
-            .collect_vec();
-        Computer {
-            program,
-            register_a,
-            register_b,
-            register_c,
-            ..Computer::default()
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Instruction::*;
+        match self {
+            Adv(operand) => write!(f, "adv {operand}"),
+            Bxl(literal) => write!(f, "bxl {literal}"),
+            Bst(operand) => write!(f, "bst {operand}"),
+            Jnz(literal) => write!(f, "jnz {literal}"),
+            Bxc => write!(f, "bxc"),
+            Out(operand) => write!(f, "out {operand}"),
+            Bdv(operand) => write!(f, "bdv {operand}"),
+            Cdv(operand) => write!(f, "cdv {operand}"),
         }
     }
+}
 
-    fn execute(&mut self) {
-        while self.instruction_pointer < self.program.len() as u8 {
-            let pointer = self.instruction_pointer as usize;
-            let (opcode, operand) = (self.program[pointer], self.program[pointer + 1]);
-            let instruction = Instruction::from(opcode);
-            if instruction.process(self, operand) {
-                self.instruction_pointer += 2;
-            }
+impl InstructionSet for Instruction {
+    const REGISTERS: &'static [&'static str] = &["A", "B", "C"];
+
+    fn decode(program: &[i64], ip: usize) -> Result<(Self, usize), VmError> {
+        use Instruction::*;
+        let operand = *program.get(ip + 1).ok_or(VmError::UnexpectedEnd { ip })?;
+        let instruction = match program[ip] {
+            0 => Adv(Operand::combo(ip, operand)?),
+            1 => Bxl(operand),
+            2 => Bst(Operand::combo(ip, operand)?),
+            3 => Jnz(operand),
+            4 => Bxc,
+            5 => Out(Operand::combo(ip, operand)?),
+            6 => Bdv(Operand::combo(ip, operand)?),
+            7 => Cdv(Operand::combo(ip, operand)?),
+            opcode => return Err(VmError::InvalidOpcode { ip, opcode }),
+        };
+        Ok((instruction, 2))
+    }
+
+    fn execute(&self, state: &mut State) -> Result<Control, VmError> {
+        use Instruction::*;
+        let [a, b, c] = [0, 1, 2];
+        match self {
+            Adv(operand) => state.registers[a] >>= operand.value(state),
+            Bxl(literal) => state.registers[b] ^= literal,
+            Bst(operand) => state.registers[b] = operand.value(state) & 0b111,
+            Jnz(literal) => {
+                if state.registers[a] != 0 {
+                    return Ok(Control::Jump(*literal as usize));
+                }
+            },
+            Bxc => state.registers[b] ^= state.registers[c],
+            Out(operand) => state.output.push(operand.value(state) & 0b111),
+            Bdv(operand) => state.registers[b] = state.registers[a] >> operand.value(state),
+            Cdv(operand) => state.registers[c] = state.registers[a] >> operand.value(state),
         }
+        Ok(Control::Next)
     }
 }
 
+fn parse(input: &str) -> Vm<Instruction> {
+    let mut lines = input.lines();
+    let mut registers = [0; 3];
+    for register in registers.iter_mut() {
+        *register = lines.next().and_then(|line| line[REGISTER_LEN..].parse::<i64>().ok()).unwrap_or_default();
+    }
+    lines.next();
+    let program = lines.next().iter()
+        .flat_map(|line| line[PROGRAM_LEN..].split(','))
+        .flat_map(|number| number.parse::<i64>().ok())
+        .collect_vec();
+    Vm::new(program, &registers)
+}
+
 pub fn part_one(input: &str) -> Option<String> {
-    let mut computer = Computer::parse(input);
-    computer.execute();
-    Some(computer.output.into_iter().join(","))
+    let mut computer = parse(input);
+    let output = computer.run(None).ok()?;
+    Some(output.iter().join(","))
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut computer = Computer::parse(input); 
-    let mut register_a_candidate = 1;
-    loop {
-        computer.register_a = register_a_candidate;
-        computer.register_b = 0;
-        computer.register_b = 0;
-        computer.instruction_pointer = 0;
-        computer.output.clear();
-        computer.execute();
-        if computer.program.ends_with(&computer.output) {
-            if computer.output.len() == computer.program.len() {
-                break;
-            }
-            register_a_candidate <<= 3;
-            continue
-        }
-        while register_a_candidate & 0b111 == 0b111 {
-            register_a_candidate >>= 3;
-        }
-        register_a_candidate += 1;
-    };
-    Some(register_a_candidate)
+    let mut computer = parse(input);
+    let program = computer.program.clone();
+    vm::reverse_search(&program, 3, |register_a| {
+        computer.reset(&[register_a as i64, 0, 0]);
+        computer.run(Some(CYCLE_LIMIT)).map(<[i64]>::to_vec).unwrap_or_default()
+    })
 }
 
 #[cfg(test)]
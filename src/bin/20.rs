@@ -0,0 +1,3 @@
+advent_of_code::solution!(20);
+pub fn part_one(_: &str) -> Option<u64> { Some(1) }
+pub fn part_two(_: &str) -> Option<u64> { std::thread::sleep(std::time::Duration::from_secs(5)); None }
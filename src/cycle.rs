@@ -0,0 +1,173 @@
+/// Cycle detection over state-transition functions.
+///
+/// Every helper in this module looks at the sequence `x0, f(x0), f(f(x0)), ...` and assumes it
+/// eventually repeats, which is the case for any simulation over a finite state space.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cycle found in a sequence of states.
+///
+/// The state at step `start` is the first one that repeats, and it repeats every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Maps a step number to the earliest step that yields the same state.
+    ///
+    /// ```
+    /// # use advent_of_code::cycle::Cycle;
+    /// let cycle = Cycle { start: 2, length: 3 };
+    /// assert_eq!(cycle.equivalent_step(1), 1);
+    /// assert_eq!(cycle.equivalent_step(9), 3);
+    /// ```
+    #[must_use]
+    pub fn equivalent_step(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle in the sequence generated by `f` using Floyd's tortoise and hare.
+///
+/// Only keeps two states in memory, at the cost of calling `f` roughly three times per step.
+pub fn floyd<T: PartialEq>(initial: T, f: impl Fn(&T) -> T) -> Cycle {
+    let mut tortoise = f(&initial);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&f(&hare));
+    }
+
+    let mut start = 0;
+    let mut tortoise = initial;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds the cycle in the sequence generated by `f` using Brent's algorithm.
+///
+/// Like [`floyd`] this runs in constant memory, but it usually needs fewer calls to `f`.
+/// Requires `T: Clone` since the initial state is walked twice.
+pub fn brent<T: PartialEq + Clone>(initial: T, f: impl Fn(&T) -> T) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = f(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Applies `f` to `initial` `steps` times, skipping ahead as soon as a state repeats.
+///
+/// Every visited state is remembered, so this trades memory for not having to call `f` more than
+/// `start + length` times. Use [`floyd`] or [`brent`] directly when states are large.
+pub fn simulate<T: Hash + Eq + Clone>(initial: T, steps: usize, mut f: impl FnMut(&T) -> T) -> T {
+    let mut seen: HashMap<T, usize> = HashMap::new();
+    let mut history = vec![initial];
+
+    for step in 0..steps {
+        let current = &history[step];
+        if let Some(&start) = seen.get(current) {
+            let cycle = Cycle {
+                start,
+                length: step - start,
+            };
+            return history.swap_remove(cycle.equivalent_step(steps));
+        }
+        seen.insert(current.clone(), step);
+        let next = f(current);
+        history.push(next);
+    }
+
+    history.pop().unwrap()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, floyd, simulate, Cycle};
+
+    // 0, 1, 2, 3, 4, 5, 2, 3, 4, 5, ...
+    fn step(x: &u32) -> u32 {
+        if *x == 5 {
+            2
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn floyd_finds_cycle() {
+        assert_eq!(floyd(0, step), Cycle { start: 2, length: 4 });
+    }
+
+    #[test]
+    fn brent_finds_cycle() {
+        assert_eq!(brent(0, step), Cycle { start: 2, length: 4 });
+    }
+
+    #[test]
+    fn finds_cycle_starting_at_initial_state() {
+        assert_eq!(brent(0, |x| (x + 1) % 7), Cycle { start: 0, length: 7 });
+        assert_eq!(floyd(0, |x| (x + 1) % 7), Cycle { start: 0, length: 7 });
+    }
+
+    #[test]
+    fn finds_fixed_point() {
+        assert_eq!(brent(3, |x| *x), Cycle { start: 0, length: 1 });
+        assert_eq!(floyd(3, |x| *x), Cycle { start: 0, length: 1 });
+    }
+
+    #[test]
+    fn simulates_without_cycle() {
+        assert_eq!(simulate(0, 0, step), 0);
+        assert_eq!(simulate(0, 4, step), 4);
+    }
+
+    #[test]
+    fn simulates_with_cycle_skip() {
+        assert_eq!(simulate(0, 6, step), 2);
+        assert_eq!(simulate(0, 1_000_000_000, step), 2 + (1_000_000_000 - 2) % 4);
+    }
+}
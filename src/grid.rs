@@ -0,0 +1,173 @@
+/// A dense two-dimensional grid, the shape most puzzle inputs come in.
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Creates a grid of `on` / `off` cells from a set of `(x, y)` points.
+    /// Points outside of the grid are ignored.
+    pub fn from_points(
+        width: usize,
+        height: usize,
+        points: impl IntoIterator<Item = (usize, usize)>,
+        on: T,
+        off: T,
+    ) -> Self {
+        let mut grid = Self::new(width, height, off);
+        for point in points {
+            grid.set(point, on.clone());
+        }
+        grid
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid by calling `f` with the position of every cell.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut((usize, usize)) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a block of text into a grid, mapping every character with `f`.
+    /// The width is taken from the first line; empty lines are skipped.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+        let cells: Vec<T> = input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .flat_map(|line| line.chars())
+            .map(&mut f)
+            .collect();
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.1 * self.width + pos.0])
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.1 * self.width + pos.0])
+    }
+
+    /// Sets the cell at `pos`, returning `false` if it lies outside of the grid.
+    pub fn set(&mut self, pos: (usize, usize), value: T) -> bool {
+        match self.get_mut(pos) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Iterates over the rows of the grid, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterates over every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| ((index % width, index / width), cell))
+    }
+
+    /// Returns a new grid with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+
+    #[test]
+    fn parses_text() {
+        let grid = Grid::parse("#..\n.#.\n", |c| c == '#');
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.get((0, 0)), Some(&true));
+        assert_eq!(grid.get((1, 1)), Some(&true));
+        assert_eq!(grid.get((2, 1)), Some(&false));
+        assert_eq!(grid.get((3, 0)), None);
+    }
+
+    #[test]
+    fn builds_from_points() {
+        let grid = Grid::from_points(3, 2, [(0, 0), (2, 1), (5, 5)], '#', '.');
+        assert_eq!(grid.to_string(), "#..\n..#");
+    }
+
+    #[test]
+    fn iterates_with_positions() {
+        let grid = Grid::from_fn(2, 2, |(x, y)| x + 2 * y);
+        let cells: Vec<_> = grid.iter().map(|(pos, value)| (pos, *value)).collect();
+        assert_eq!(
+            cells,
+            vec![((0, 0), 0), ((1, 0), 1), ((0, 1), 2), ((1, 1), 3)]
+        );
+    }
+}
@@ -1,5 +1,12 @@
 pub mod template;
 
+pub mod cycle;
+pub mod grid;
+pub mod memo;
+pub mod picture;
+pub mod range_set;
+pub mod vm;
+
 // https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
 pub fn extended_gcd(a: i64, b: i64) -> (i64, (i64, i64)) {
     let (mut old_s, mut s) = (1, 0);
@@ -7,8 +7,11 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
+    use advent_of_code::template::selection::{parse_days, DaySelection};
+    use advent_of_code::template::visualize::Format;
     use advent_of_code::template::Day;
     use std::process;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
@@ -26,14 +29,20 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
+            visualize: bool,
+            record: Option<Format>,
+            fps: Option<u32>,
             submit: Option<u8>,
         },
         All {
+            selection: DaySelection,
             release: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
         },
         Time {
+            selection: DaySelection,
             all: bool,
-            day: Option<Day>,
             store: bool,
         },
         #[cfg(feature = "today")]
@@ -44,17 +53,28 @@ mod args {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+            Some("all") => {
+                let release = args.contains("--release");
+                let jobs = args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1);
+                let timeout = args
+                    .opt_value_from_str("--timeout")?
+                    .map(Duration::from_secs_f64);
+
+                AppArguments::All {
+                    release,
+                    jobs,
+                    timeout,
+                    selection: parse_selection(&mut args)?,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
                     store,
+                    selection: parse_selection(&mut args)?,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -73,6 +93,9 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                visualize: args.contains("--visualize"),
+                record: args.opt_value_from_str("--record")?,
+                fps: args.opt_value_from_str("--fps")?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -93,6 +116,17 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses the day filters shared by `all` and `time`. Needs to run after all other flags
+    /// have been consumed, since the day-set expression is a free argument.
+    fn parse_selection(args: &mut pico_args::Arguments) -> Result<DaySelection, pico_args::Error> {
+        Ok(DaySelection {
+            unsolved: args.contains("--unsolved"),
+            slowest: args.opt_value_from_str("--slowest")?,
+            changed: args.contains("--changed"),
+            days: args.opt_free_from_fn(parse_days)?,
+        })
+    }
 }
 
 fn main() {
@@ -102,8 +136,17 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                selection,
+                release,
+                jobs,
+                timeout,
+            } => all::handle(&selection, release, jobs, timeout),
+            AppArguments::Time {
+                selection,
+                all,
+                store,
+            } => time::handle(selection, all, store),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
@@ -120,8 +163,11 @@ fn main() {
                 day,
                 release,
                 dhat,
+                visualize,
+                record,
+                fps,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+            } => solve::handle(day, release, dhat, visualize, record, fps, submit),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
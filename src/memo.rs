@@ -0,0 +1,87 @@
+/// Memoization for recursive functions.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Caches the results of a recursive function by its argument.
+///
+/// The wrapped function receives the [`Memo`] itself, so recursive calls go through the cache:
+///
+/// ```
+/// # use advent_of_code::memo::Memo;
+/// fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+///     if n < 2 {
+///         return n;
+///     }
+///     memo.get(n - 1) + memo.get(n - 2)
+/// }
+///
+/// let mut memo = Memo::new(fib);
+/// assert_eq!(memo.get(90), 2_880_067_194_370_816_120);
+/// ```
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    func: fn(&mut Memo<K, V>, K) -> V,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    pub fn new(func: fn(&mut Memo<K, V>, K) -> V) -> Self {
+        Self {
+            cache: HashMap::new(),
+            func,
+        }
+    }
+
+    /// Returns the cached value for `key`, computing and storing it first if needed.
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+        let value = (self.func)(self, key.clone());
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Number of cached results.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Drops every cached result while keeping the wrapped function.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Memo;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get(n - 1) + memo.get(n - 2)
+    }
+
+    #[test]
+    fn computes_recursive_values() {
+        let mut memo = Memo::new(fib);
+        assert_eq!(memo.get(10), 55);
+        assert_eq!(memo.get(90), 2_880_067_194_370_816_120);
+    }
+
+    #[test]
+    fn caches_every_subproblem() {
+        let mut memo = Memo::new(fib);
+        memo.get(20);
+        assert_eq!(memo.len(), 21);
+        memo.clear();
+        assert!(memo.is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+/// Heuristics for "find the picture" puzzles, where a simulation briefly forms an image.
+///
+/// Each scorer looks at the set of occupied points of a single frame. [`find_frame`] runs a
+/// simulation and returns the frame that looks the most like a picture according to one of them.
+use std::collections::{HashMap, HashSet};
+
+pub type Point = (isize, isize);
+
+/// Side length of the square blocks points are binned into for [`entropy`].
+const ENTROPY_BLOCK: isize = 4;
+
+/// Shannon entropy (in bits) of the distribution of points over `block` x `block` squares.
+///
+/// Random noise spreads evenly over all blocks, while a picture concentrates points in a few of
+/// them, so lower values mean more structure.
+pub fn entropy(points: &[Point], block: isize) -> f64 {
+    let mut bins: HashMap<Point, usize> = HashMap::new();
+    for (x, y) in points {
+        *bins
+            .entry((x.div_euclid(block), y.div_euclid(block)))
+            .or_default() += 1;
+    }
+
+    let total = points.len() as f64;
+    bins.values()
+        .map(|count| {
+            let p = *count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Variance of the x and y coordinates. Lower values mean the points are bunched together.
+pub fn variance(points: &[Point]) -> (f64, f64) {
+    if points.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let n = points.len() as f64;
+    let (sum_x, sum_y) = points.iter().fold((0.0, 0.0), |(sx, sy), (x, y)| {
+        (sx + *x as f64, sy + *y as f64)
+    });
+    let (mean_x, mean_y) = (sum_x / n, sum_y / n);
+
+    points.iter().fold((0.0, 0.0), |(vx, vy), (x, y)| {
+        let (dx, dy) = (*x as f64 - mean_x, *y as f64 - mean_y);
+        (vx + dx * dx / n, vy + dy * dy / n)
+    })
+}
+
+/// Size of the largest group of orthogonally adjacent points.
+pub fn largest_component(points: &[Point]) -> usize {
+    let mut unvisited: HashSet<Point> = points.iter().copied().collect();
+    let mut largest = 0;
+
+    while let Some(&start) = unvisited.iter().next() {
+        unvisited.remove(&start);
+        let mut stack = vec![start];
+        let mut size = 0;
+        while let Some((x, y)) = stack.pop() {
+            size += 1;
+            for neighbour in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if unvisited.remove(&neighbour) {
+                    stack.push(neighbour);
+                }
+            }
+        }
+        largest = largest.max(size);
+    }
+
+    largest
+}
+
+/// Length of the longest horizontal line of consecutive points.
+pub fn longest_run(points: &[Point]) -> usize {
+    let mut sorted: Vec<Point> = points.iter().map(|(x, y)| (*y, *x)).collect();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<Point> = None;
+    for (y, x) in sorted {
+        run = match previous {
+            Some((py, px)) if py == y && px + 1 == x => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some((y, x));
+    }
+
+    longest
+}
+
+/// The heuristic used to decide how picture-like a frame is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scorer {
+    /// Lowest [`entropy`].
+    Entropy,
+    /// Lowest sum of the x and y [`variance`].
+    Variance,
+    /// Biggest [`largest_component`].
+    LargestComponent,
+    /// Longest [`longest_run`].
+    LongestRun,
+}
+
+impl Scorer {
+    /// Scores a frame so that higher values always mean a more picture-like frame.
+    pub fn score(&self, points: &[Point]) -> f64 {
+        match self {
+            Scorer::Entropy => -entropy(points, ENTROPY_BLOCK),
+            Scorer::Variance => {
+                let (x, y) = variance(points);
+                -(x + y)
+            }
+            Scorer::LargestComponent => largest_component(points) as f64,
+            Scorer::LongestRun => longest_run(points) as f64,
+        }
+    }
+}
+
+/// Runs a simulation for `frames` frames and returns the index of the frame that scores best.
+///
+/// Frame `0` is `state` as passed in, `step` advances the simulation by one frame and `points`
+/// extracts the occupied points of the current frame. Ties go to the earliest frame.
+pub fn find_frame<S>(
+    mut state: S,
+    frames: usize,
+    mut step: impl FnMut(&mut S),
+    points: impl Fn(&S) -> Vec<Point>,
+    scorer: Scorer,
+) -> usize {
+    let mut best = (0, scorer.score(&points(&state)));
+    for index in 1..frames {
+        step(&mut state);
+        let score = scorer.score(&points(&state));
+        if score > best.1 {
+            best = (index, score);
+        }
+    }
+    best.0
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{entropy, find_frame, largest_component, longest_run, variance, Point, Scorer};
+
+    fn scattered() -> Vec<Point> {
+        (0..16).map(|i| ((i * 7) % 31, (i * 13) % 29)).collect()
+    }
+
+    fn square() -> Vec<Point> {
+        (0..16).map(|i| (8 + i % 4, 8 + i / 4)).collect()
+    }
+
+    #[test]
+    fn measures_entropy() {
+        assert_eq!(entropy(&square(), 4), 0.0);
+        assert!(entropy(&scattered(), 4) > 3.0);
+    }
+
+    #[test]
+    fn measures_variance() {
+        assert_eq!(variance(&[(1, 5), (3, 5)]), (1.0, 0.0));
+        assert_eq!(variance(&[]), (0.0, 0.0));
+    }
+
+    #[test]
+    fn measures_components_and_runs() {
+        assert_eq!(largest_component(&square()), 16);
+        assert_eq!(largest_component(&[(0, 0), (1, 1), (2, 2)]), 1);
+        assert_eq!(longest_run(&square()), 4);
+        assert_eq!(longest_run(&[(0, 0), (1, 0), (3, 0), (4, 0), (5, 0)]), 3);
+    }
+
+    #[test]
+    fn finds_picture_frame() {
+        // points move from a scattered layout into a square at frame 3 and then disperse again.
+        let frames = [scattered(), scattered(), scattered(), square(), scattered()];
+        for scorer in [
+            Scorer::Entropy,
+            Scorer::Variance,
+            Scorer::LargestComponent,
+            Scorer::LongestRun,
+        ] {
+            let index = find_frame(0, frames.len(), |i| *i += 1, |i| frames[*i].clone(), scorer);
+            assert_eq!(index, 3, "{scorer:?}");
+        }
+    }
+}
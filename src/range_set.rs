@@ -0,0 +1,260 @@
+/// A set of values stored as disjoint, half-open ranges.
+///
+/// Overlapping and adjacent ranges are merged on insertion and split on removal, so iteration
+/// always yields the smallest number of ranges covering the set, in order.
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::{Range, Sub};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    /// Range starts mapped to their ends.
+    ranges: BTreeMap<T, T>,
+    /// Range starts grouped by the length of their range, for first-fit lookups.
+    by_len: BTreeMap<T, BTreeSet<T>>,
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> RangeSet<T> {
+    pub fn new() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+            by_len: BTreeMap::new(),
+        }
+    }
+
+    fn add(&mut self, start: T, end: T) {
+        self.ranges.insert(start, end);
+        self.by_len.entry(end - start).or_default().insert(start);
+    }
+
+    fn take(&mut self, start: T) -> T {
+        let end = self.ranges.remove(&start).unwrap();
+        let len = end - start;
+        let starts = self.by_len.get_mut(&len).unwrap();
+        starts.remove(&start);
+        if starts.is_empty() {
+            self.by_len.remove(&len);
+        }
+        end
+    }
+
+    /// Adds a range, merging it with every range it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let (mut start, mut end) = (range.start, range.end);
+
+        if let Some((&prev_start, &prev_end)) = self.ranges.range(..=start).next_back() {
+            if prev_end >= start {
+                start = prev_start;
+                end = end.max(self.take(prev_start));
+            }
+        }
+
+        while let Some((&next_start, _)) = self.ranges.range(start..=end).next() {
+            end = end.max(self.take(next_start));
+        }
+
+        self.add(start, end);
+    }
+
+    /// Removes a range, splitting any range that only partially overlaps it.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        if let Some((&prev_start, &prev_end)) = self.ranges.range(..range.start).next_back() {
+            if prev_end > range.start {
+                self.take(prev_start);
+                self.add(prev_start, range.start);
+                if prev_end > range.end {
+                    self.add(range.end, prev_end);
+                }
+            }
+        }
+
+        while let Some((&next_start, _)) = self.ranges.range(range.start..range.end).next() {
+            let next_end = self.take(next_start);
+            if next_end > range.end {
+                self.add(range.end, next_end);
+            }
+        }
+    }
+
+    /// Returns the range containing `value`, if any.
+    pub fn get(&self, value: T) -> Option<Range<T>> {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .filter(|(_, end)| **end > value)
+            .map(|(start, end)| *start..*end)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.get(value).is_some()
+    }
+
+    /// Returns the leftmost range that is at least `len` long.
+    ///
+    /// Runs in `O(k log n)`, where `k` is the number of distinct range lengths.
+    pub fn first_fit(&self, len: T) -> Option<Range<T>> {
+        self.by_len
+            .range(len..)
+            .filter_map(|(_, starts)| starts.first())
+            .min()
+            .map(|start| *start..self.ranges[start])
+    }
+
+    /// Splits the set at `at`, returning everything at or after it.
+    pub fn split_off(&mut self, at: T) -> Self {
+        let mut after = Self::new();
+        if let Some(range) = self.get(at) {
+            self.remove(at..range.end);
+            after.add(at, range.end);
+        }
+        let starts: Vec<T> = self.ranges.range(at..).map(|(start, _)| *start).collect();
+        for start in starts {
+            let end = self.take(start);
+            after.add(start, end);
+        }
+        after
+    }
+
+    /// Adds every range of `other` to the set.
+    pub fn union(&mut self, other: &Self) {
+        for range in other.iter() {
+            self.insert(range);
+        }
+    }
+
+    /// Removes every range of `other` from the set.
+    pub fn difference(&mut self, other: &Self) {
+        for range in other.iter() {
+            self.remove(range);
+        }
+    }
+
+    /// Iterates over the ranges of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().map(|(start, end)| *start..*end)
+    }
+
+    /// Iterates over the gaps between the ranges of the set that lie within `bounds`.
+    pub fn gaps(&self, bounds: Range<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        let mut cursor = bounds.start;
+        self.iter()
+            .map(Some)
+            .chain([None])
+            .filter_map(move |range| {
+                let gap_end = range
+                    .as_ref()
+                    .map_or(bounds.end, |r| r.start.min(bounds.end));
+                let gap = (cursor < gap_end).then_some(cursor..gap_end);
+                if let Some(range) = range {
+                    cursor = cursor.max(range.end);
+                }
+                gap
+            })
+    }
+
+    /// Number of disjoint ranges in the set.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::RangeSet;
+    use std::ops::Range;
+
+    fn ranges(set: &RangeSet<u32>) -> Vec<Range<u32>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn merges_overlapping_and_adjacent_ranges() {
+        let set: RangeSet<u32> = [5..8, 1..3, 3..4, 7..10, 20..25].into_iter().collect();
+        assert_eq!(ranges(&set), vec![1..4, 5..10, 20..25]);
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn merges_range_spanning_others() {
+        let mut set: RangeSet<u32> = [1..2, 4..5, 7..8].into_iter().collect();
+        set.insert(0..10);
+        assert_eq!(ranges(&set), vec![0..10]);
+        assert_eq!(set.first_fit(10), Some(0..10));
+    }
+
+    #[test]
+    fn removes_and_splits_ranges() {
+        let mut set: RangeSet<u32> = [0..10, 12..20].into_iter().collect();
+        set.remove(3..5);
+        set.remove(8..14);
+        assert_eq!(ranges(&set), vec![0..3, 5..8, 14..20]);
+        assert!(set.contains(2));
+        assert!(!set.contains(3));
+        assert_eq!(set.get(15), Some(14..20));
+    }
+
+    #[test]
+    fn finds_first_fit() {
+        let set: RangeSet<u32> = [0..1, 3..5, 8..11, 15..17].into_iter().collect();
+        assert_eq!(set.first_fit(1), Some(0..1));
+        assert_eq!(set.first_fit(2), Some(3..5));
+        assert_eq!(set.first_fit(3), Some(8..11));
+        assert_eq!(set.first_fit(4), None);
+    }
+
+    #[test]
+    fn keeps_first_fit_index_in_sync() {
+        let mut set: RangeSet<u32> = [0..2, 4..8].into_iter().collect();
+        set.remove(4..6);
+        assert_eq!(set.first_fit(2), Some(0..2));
+        set.remove(0..1);
+        assert_eq!(set.first_fit(2), Some(6..8));
+        set.insert(2..4);
+        assert_eq!(set.first_fit(3), Some(1..4));
+    }
+
+    #[test]
+    fn splits_off_tail() {
+        let mut set: RangeSet<u32> = [0..5, 8..10].into_iter().collect();
+        let tail = set.split_off(3);
+        assert_eq!(ranges(&set), vec![0..3]);
+        assert_eq!(ranges(&tail), vec![3..5, 8..10]);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let mut set: RangeSet<u32> = [0..5].into_iter().collect();
+        set.union(&[10..15].into_iter().collect());
+        set.difference(&[2..12].into_iter().collect());
+        assert_eq!(ranges(&set), vec![0..2, 12..15]);
+    }
+
+    #[test]
+    fn iterates_gaps() {
+        let set: RangeSet<u32> = [2..4, 6..8].into_iter().collect();
+        assert_eq!(set.gaps(0..10).collect::<Vec<_>>(), vec![0..2, 4..6, 8..10]);
+        assert_eq!(set.gaps(3..7).collect::<Vec<_>>(), vec![4..6]);
+    }
+}
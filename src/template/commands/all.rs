@@ -1,5 +1,18 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::run_multi::run_multi;
+use crate::template::selection::DaySelection;
+use crate::template::summary;
+use crate::template::timings::Timings;
+
+pub fn handle(selection: &DaySelection, is_release: bool, jobs: usize, timeout: Option<Duration>) {
+    let days_to_run = selection.resolve(&Timings::read_from_file());
+    let run = run_multi(&days_to_run, is_release, false, jobs, timeout);
+
+    summary::print(&run.summaries);
+
+    if run.summaries.iter().any(summary::DaySummary::is_failure) {
+        process::exit(1);
+    }
 }
@@ -1,21 +1,36 @@
 use std::process::{Command, Stdio};
 
+use crate::template::visualize::Format;
 use crate::template::Day;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    visualize: bool,
+    record: Option<Format>,
+    fps: Option<u32>,
+    submit_part: Option<u8>,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
+    let mut features = vec![];
+
+    if visualize || record.is_some() {
+        features.push("visualize");
+    }
+
     if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
+        features.push("dhat-heap");
+        cmd_args.extend(["--profile".to_string(), "dhat".to_string()]);
     } else if release {
         cmd_args.push("--release".to_string());
     }
 
+    if !features.is_empty() {
+        cmd_args.extend(["--features".to_string(), features.join(",")]);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,6 +38,20 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if visualize {
+        cmd_args.push("--visualize".to_string());
+    }
+
+    if let Some(record) = record {
+        cmd_args.push("--record".to_string());
+        cmd_args.push(record.to_string());
+    }
+
+    if let Some(fps) = fps {
+        cmd_args.push("--fps".to_string());
+        cmd_args.push(fps.to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
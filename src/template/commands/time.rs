@@ -1,29 +1,36 @@
-use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::template::readme_benchmarks;
 use crate::template::run_multi::run_multi;
+use crate::template::selection::DaySelection;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(selection: DaySelection, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
-    );
+    let selection = if selection.is_empty() && !run_all {
+        // when neither days nor the `--all` flag are set, filter out days that are fully benched.
+        DaySelection {
+            unsolved: true,
+            ..selection
+        }
+    } else {
+        selection
+    };
+
+    let days_to_run = selection.resolve(&stored_timings);
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings = run_multi(&days_to_run, true, true, 1, None).timings;
 
     if store {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .ok();
+        for timing in &mut timings.data {
+            timing.timestamp = now;
+        }
+
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
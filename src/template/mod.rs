@@ -3,6 +3,9 @@ use std::{env, fs};
 pub mod aoc_cli;
 pub mod commands;
 pub mod runner;
+pub mod selection;
+pub mod summary;
+pub mod visualize;
 
 pub use day::*;
 
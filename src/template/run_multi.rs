@@ -1,49 +1,98 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::HashSet,
+    io,
+    time::{Duration, Instant},
+};
 
 use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    summary::DaySummary,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// The outcome of running a set of days.
+pub struct MultiRun {
+    pub summaries: Vec<DaySummary>,
+    pub timings: Timings,
+}
 
-    let mut need_space = false;
+/// Runs the solutions for a set of days.
+///
+/// With `jobs > 1`, untimed runs execute concurrently; each day's output is buffered and printed
+/// in day order. Timed runs always execute one after another to keep measurements clean.
+/// Days that run longer than `timeout` are killed.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+) -> MultiRun {
+    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut summaries: Vec<DaySummary> = Vec::with_capacity(days_to_run.len());
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let mut report = |day: Day, output: &child_commands::RunOutput| {
+        let stdout = output.stdout();
+        match output.status {
+            child_commands::RunStatus::NotScaffolded => println!("Not solved."),
+            child_commands::RunStatus::TimedOut => println!("Timed out."),
+            _ => {
+                let val = child_commands::parse_exec_time(&stdout, day);
+                timings.push(val);
             }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        }
+        summaries.push(DaySummary::new(day, output.status, &stdout, output.elapsed));
+    };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let timer = Instant::now();
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
+    if is_timed || jobs <= 1 {
+        for (index, day) in days.iter().enumerate() {
+            print_header(*day, index > 0);
+            let output =
+                child_commands::run_solution(*day, is_timed, is_release, timeout, true).unwrap();
+            report(*day, &output);
+        }
+    } else {
+        child_commands::build_solutions(is_release).unwrap();
+        child_commands::run_parallel(&days, is_release, jobs, timeout, |index, day, output| {
+            print_header(day, index > 0);
+            child_commands::replay(&output.lines);
+            report(day, &output);
         });
+    }
+
+    let wall_time = timer.elapsed();
+    let timings = Timings { data: timings };
 
     if is_timed {
-        let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
     } else {
-        None
+        println!(
+            "\n{ANSI_BOLD}Wall time:{ANSI_RESET} {ANSI_ITALIC}{wall_time:.2?} for {} day(s) with {} job(s){ANSI_RESET}",
+            days.len(),
+            jobs.max(1)
+        );
+    }
+
+    MultiRun { summaries, timings }
+}
+
+fn print_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
     }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
 }
 
 #[allow(dead_code)]
@@ -70,17 +119,144 @@ pub mod child_commands {
     use super::{get_path_for_bin, Error};
     use crate::template::Day;
     use std::{
+        collections::BTreeMap,
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{Child, Command, Stdio},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc, Arc, Mutex,
+        },
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// A line of output captured from a child process.
+    pub enum Line {
+        Stdout(String),
+        Stderr(String),
+    }
+
+    /// How a solution bin finished.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum RunStatus {
+        NotScaffolded,
+        Success,
+        Failed,
+        TimedOut,
+    }
+
+    /// Everything a solution bin printed, along with how and how fast it finished.
+    pub struct RunOutput {
+        pub lines: Vec<Line>,
+        pub status: RunStatus,
+        pub elapsed: Duration,
+    }
+
+    impl RunOutput {
+        pub fn stdout(&self) -> Vec<String> {
+            self.lines
+                .iter()
+                .filter_map(|line| match line {
+                    Line::Stdout(line) => Some(line.clone()),
+                    Line::Stderr(_) => None,
+                })
+                .collect()
+        }
+    }
+
+    /// Compile all solution binaries up front so that parallel runs don't contend for the build lock.
+    pub fn build_solutions(is_release: bool) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        Command::new("cargo").args(&args).status()?;
+        Ok(())
+    }
+
+    /// Run the solution bins for a set of days on `jobs` worker threads.
+    /// `on_done` is called with each day's captured output, in the order of `days`.
+    pub fn run_parallel(
+        days: &[Day],
+        is_release: bool,
+        jobs: usize,
+        timeout: Option<Duration>,
+        mut on_done: impl FnMut(usize, Day, RunOutput),
+    ) {
+        // NOTE: reversed, so that popping hands out days in ascending order.
+        let queue = Arc::new(Mutex::new(
+            days.iter().copied().enumerate().rev().collect::<Vec<_>>(),
+        ));
+        let (sender, receiver) = mpsc::channel();
+
+        let workers: Vec<_> = (0..jobs.min(days.len()))
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                let sender = sender.clone();
+                thread::spawn(move || loop {
+                    let Some((index, day)) = queue.lock().unwrap().pop() else {
+                        break;
+                    };
+                    let output = run_solution(day, false, is_release, timeout, false)
+                        .unwrap_or_else(|e| RunOutput {
+                            lines: vec![Line::Stderr(format!("Failed to run day {day}: {e:?}"))],
+                            status: RunStatus::Failed,
+                            elapsed: Duration::ZERO,
+                        });
+                    if sender.send((index, day, output)).is_err() {
+                        break;
+                    }
+                })
+            })
+            .collect();
+
+        drop(sender);
+
+        // print finished days as soon as every day before them has been printed as well.
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        for (index, day, output) in receiver {
+            pending.insert(index, (day, output));
+            while let Some((day, output)) = pending.remove(&next) {
+                on_done(next, day, output);
+                next += 1;
+            }
+        }
+
+        for worker in workers {
+            worker.join().unwrap();
+        }
+    }
+
+    /// Forward captured output to stdout/stderr.
+    pub fn replay(lines: &[Line]) {
+        for line in lines {
+            match line {
+                Line::Stdout(line) => println!("{line}"),
+                Line::Stderr(line) => eprintln!("{line}"),
+            }
+        }
+    }
+
+    /// Run the solution bin for a given day.
+    /// With `forward` set, output is printed while it is captured; otherwise it is only captured.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        timeout: Option<Duration>,
+        forward: bool,
+    ) -> Result<RunOutput, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(RunOutput {
+                lines: vec![],
+                status: RunStatus::NotScaffolded,
+                elapsed: Duration::ZERO,
+            });
         }
 
         let day_padded = day.to_string();
@@ -97,35 +273,100 @@ pub mod child_commands {
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing the lines.
 
-        let mut cmd = Command::new("cargo")
+        let mut command = Command::new("cargo");
+        command
             .args(&args)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+            .stderr(Stdio::piped());
+
+        // run in a separate process group so that a timeout can take down the solution bin along with cargo.
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+        let timer = Instant::now();
+        let mut cmd = command.spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let cmd = Arc::new(Mutex::new(cmd));
+        let done = Arc::new(AtomicBool::new(false));
+        let timed_out = Arc::new(AtomicBool::new(false));
+
+        let watchdog = timeout.map(|timeout| {
+            let cmd = Arc::clone(&cmd);
+            let done = Arc::clone(&done);
+            let timed_out = Arc::clone(&timed_out);
+            thread::spawn(move || {
+                while timer.elapsed() < timeout {
+                    if done.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    thread::sleep(Duration::from_millis(10));
+                }
+                timed_out.store(true, Ordering::Relaxed);
+                kill(&mut cmd.lock().unwrap());
+            })
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let lines = Arc::new(Mutex::new(vec![]));
+
+        let thread = {
+            let lines = Arc::clone(&lines);
+            thread::spawn(move || {
+                stderr.lines().map_while(Result::ok).for_each(|line| {
+                    if forward {
+                        eprintln!("{line}");
+                    }
+                    lines.lock().unwrap().push(Line::Stderr(line));
+                });
+            })
+        };
+
+        for line in stdout.lines().map_while(Result::ok) {
+            if forward {
+                println!("{line}");
+            }
+            lines.lock().unwrap().push(Line::Stdout(line));
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let exit_status = cmd.lock().unwrap().wait()?;
+        let elapsed = timer.elapsed();
+
+        done.store(true, Ordering::Relaxed);
+        if let Some(watchdog) = watchdog {
+            watchdog.join().unwrap();
+        }
+
+        let status = if timed_out.load(Ordering::Relaxed) {
+            RunStatus::TimedOut
+        } else if exit_status.success() {
+            RunStatus::Success
+        } else {
+            RunStatus::Failed
+        };
+
+        let lines = std::mem::take(&mut *lines.lock().unwrap());
+        Ok(RunOutput {
+            lines,
+            status,
+            elapsed,
+        })
+    }
+
+    #[cfg(unix)]
+    fn kill(cmd: &mut Child) {
+        let _ = Command::new("kill")
+            .args(["-KILL", &format!("-{}", cmd.id())])
+            .status();
+    }
 
-        Ok(output)
+    #[cfg(not(unix))]
+    fn kill(cmd: &mut Child) {
+        let _ = cmd.kill();
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -134,6 +375,7 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            timestamp: None,
         };
 
         output
@@ -174,7 +416,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
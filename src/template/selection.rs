@@ -0,0 +1,177 @@
+/// Parsing and resolving the set of days that `all` and `time` operate on.
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::time::UNIX_EPOCH;
+
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::Timings;
+use crate::template::{all_days, Day};
+
+/// Parses a day-set expression such as `1-5`, `3,7,12` or `1-3,10`.
+pub fn parse_days(s: &str) -> Result<HashSet<Day>, DaySelectionError> {
+    let mut days = HashSet::new();
+
+    for part in s.split(',').map(str::trim) {
+        if let Some((from, to)) = part.split_once('-') {
+            let from: Day = from
+                .trim()
+                .parse()
+                .map_err(|_| DaySelectionError(part.into()))?;
+            let to: Day = to
+                .trim()
+                .parse()
+                .map_err(|_| DaySelectionError(part.into()))?;
+            if from > to {
+                return Err(DaySelectionError(part.into()));
+            }
+            days.extend(all_days().filter(|day| *day >= from && *day <= to));
+        } else {
+            days.insert(part.parse().map_err(|_| DaySelectionError(part.into()))?);
+        }
+    }
+
+    Ok(days)
+}
+
+/// An error which can be returned when parsing a day-set expression.
+#[derive(Debug)]
+pub struct DaySelectionError(String);
+
+impl Error for DaySelectionError {}
+
+impl Display for DaySelectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection `{}`, expecting days between 1 and 25 like `1-5` or `3,7,12`",
+            self.0
+        )
+    }
+}
+
+/// Which days to run, given as an optional day-set expression narrowed down by filters.
+#[derive(Debug, Default)]
+pub struct DaySelection {
+    /// Days from a day-set expression. `None` selects every day.
+    pub days: Option<HashSet<Day>>,
+    /// Only days that do not have stored timings for both parts.
+    pub unsolved: bool,
+    /// Only the `n` days with the slowest stored timings.
+    pub slowest: Option<usize>,
+    /// Only days whose solution changed after their timing was last stored.
+    pub changed: bool,
+}
+
+impl DaySelection {
+    /// Returns `true` if neither a day-set expression nor a filter was given.
+    pub fn is_empty(&self) -> bool {
+        self.days.is_none() && !self.unsolved && self.slowest.is_none() && !self.changed
+    }
+
+    pub fn resolve(&self, timings: &Timings) -> HashSet<Day> {
+        let mut days: HashSet<Day> = match &self.days {
+            Some(days) => days.clone(),
+            None => all_days().collect(),
+        };
+
+        if self.unsolved {
+            days.retain(|day| !timings.is_day_complete(*day));
+        }
+
+        if self.changed {
+            days.retain(|day| is_changed(*day, timings));
+        }
+
+        if let Some(n) = self.slowest {
+            let mut timed: Vec<_> = timings
+                .data
+                .iter()
+                .filter(|t| days.contains(&t.day))
+                .collect();
+            timed.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+            days = timed.iter().take(n).map(|t| t.day).collect();
+        }
+
+        days
+    }
+}
+
+/// A day counts as changed if its solution was modified after its timing was stored,
+/// or if it has never been timed.
+fn is_changed(day: Day, timings: &Timings) -> bool {
+    let Some(modified) = fs::metadata(get_path_for_bin(day))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+    else {
+        return false;
+    };
+
+    timings
+        .data
+        .iter()
+        .find(|t| t.day == day)
+        .and_then(|t| t.timestamp)
+        .is_none_or(|stored| modified.as_secs() >= stored)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_days, DaySelection};
+    use crate::day;
+    use crate::template::timings::{Timing, Timings};
+
+    fn timing(day: crate::template::Day, total_nanos: f64, complete: bool) -> Timing {
+        Timing {
+            day,
+            part_1: Some("1ms".into()),
+            part_2: complete.then(|| "1ms".into()),
+            total_nanos,
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn parses_expressions() {
+        assert_eq!(parse_days("1-5").unwrap().len(), 5);
+        assert_eq!(
+            parse_days("3,7,12").unwrap(),
+            [day!(3), day!(7), day!(12)].into()
+        );
+        assert_eq!(
+            parse_days("1-2, 25").unwrap(),
+            [day!(1), day!(2), day!(25)].into()
+        );
+        assert!(parse_days("0-3").is_err());
+        assert!(parse_days("5-1").is_err());
+        assert!(parse_days("abc").is_err());
+    }
+
+    #[test]
+    fn resolves_filters() {
+        let timings = Timings {
+            data: vec![
+                timing(day!(1), 10.0, true),
+                timing(day!(2), 30.0, false),
+                timing(day!(3), 20.0, true),
+            ],
+        };
+
+        let selection = DaySelection {
+            days: Some(parse_days("1-3").unwrap()),
+            unsolved: true,
+            ..DaySelection::default()
+        };
+        assert_eq!(selection.resolve(&timings), [day!(2)].into());
+
+        let selection = DaySelection {
+            slowest: Some(2),
+            ..DaySelection::default()
+        };
+        assert_eq!(selection.resolve(&timings), [day!(2), day!(3)].into());
+    }
+}
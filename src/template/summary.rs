@@ -0,0 +1,204 @@
+/// The summary table printed at the end of `cargo all`.
+use std::time::Duration;
+
+use crate::template::run_multi::child_commands::RunStatus;
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// The result of a single part, as printed by its solution bin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part returned an answer. Multi-line answers are shown as `▼`.
+    Solved(String),
+    /// The part returned `None`.
+    Unsolved,
+    /// The part never reported a result, e.g. because the bin panicked before getting to it.
+    Missing,
+}
+
+/// The outcome of running one day.
+#[derive(Debug, Clone)]
+pub struct DaySummary {
+    pub day: Day,
+    pub status: RunStatus,
+    pub parts: [PartStatus; 2],
+    pub duration: Duration,
+}
+
+impl DaySummary {
+    pub fn new(day: Day, status: RunStatus, stdout: &[String], duration: Duration) -> Self {
+        Self {
+            day,
+            status,
+            parts: [parse_part(stdout, 1), parse_part(stdout, 2)],
+            duration,
+        }
+    }
+
+    /// Number of parts that returned an answer.
+    pub fn stars(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|part| matches!(part, PartStatus::Solved(_)))
+            .count()
+    }
+
+    /// A day fails if it is scaffolded but did not run to completion or a part returned `None`.
+    pub fn is_failure(&self) -> bool {
+        match self.status {
+            RunStatus::NotScaffolded => false,
+            RunStatus::Failed | RunStatus::TimedOut => true,
+            RunStatus::Success => self
+                .parts
+                .iter()
+                .any(|part| !matches!(part, PartStatus::Solved(_))),
+        }
+    }
+
+    fn cell(&self, part: usize) -> String {
+        match (self.status, &self.parts[part]) {
+            (RunStatus::NotScaffolded, _) => "not scaffolded".into(),
+            (_, PartStatus::Solved(answer)) => answer.clone(),
+            (_, PartStatus::Unsolved) => "✖".into(),
+            (RunStatus::TimedOut, PartStatus::Missing) => "timed out".into(),
+            (RunStatus::Failed, PartStatus::Missing) => "panicked".into(),
+            (RunStatus::Success, PartStatus::Missing) => "-".into(),
+        }
+    }
+}
+
+/// Extracts the answer of a part from the output of a solution bin.
+///
+/// Single-line answers are printed in bold, which separates them from the timing that follows.
+fn parse_part(stdout: &[String], part: u8) -> PartStatus {
+    let prefix = format!("Part {part}: ");
+
+    stdout
+        .iter()
+        .filter_map(|line| line.rsplit('\r').next())
+        .find_map(|line| {
+            let result = line.strip_prefix(&prefix)?;
+            if let Some((_, answer)) = result.split_once(ANSI_BOLD) {
+                let answer = answer.split(ANSI_RESET).next().unwrap_or_default();
+                return Some(PartStatus::Solved(answer.into()));
+            }
+            Some(match result.trim().chars().next() {
+                Some('▼') => PartStatus::Solved("▼".into()),
+                _ => PartStatus::Unsolved,
+            })
+        })
+        .unwrap_or(PartStatus::Missing)
+}
+
+/// Prints a table with the status of every part along with the number of collected stars.
+pub fn print(summaries: &[DaySummary]) {
+    let rows: Vec<[String; 4]> = summaries
+        .iter()
+        .map(|summary| {
+            let duration = match summary.status {
+                RunStatus::NotScaffolded => String::new(),
+                _ => format!("{:.2?}", summary.duration),
+            };
+            [
+                summary.day.to_string(),
+                summary.cell(0),
+                summary.cell(1),
+                duration,
+            ]
+        })
+        .collect();
+
+    let header = ["Day", "Part 1", "Part 2", "Time"];
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([header[i].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let format_row = |row: &[&str]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    };
+
+    println!();
+    println!("{ANSI_BOLD}{}{ANSI_RESET}", format_row(&header));
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-|-")
+    );
+    for row in &rows {
+        println!("{}", format_row(&row.each_ref().map(String::as_str)));
+    }
+
+    let stars: usize = summaries.iter().map(DaySummary::stars).sum();
+    println!(
+        "\n{ANSI_BOLD}Stars:{ANSI_RESET} ⭐ {stars}/{}",
+        summaries.len() * 2
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{DaySummary, PartStatus};
+    use crate::day;
+    use crate::template::run_multi::child_commands::RunStatus;
+
+    fn summary(status: RunStatus, stdout: &[&str]) -> DaySummary {
+        let stdout: Vec<String> = stdout.iter().map(|line| line.to_string()).collect();
+        DaySummary::new(day!(1), status, &stdout, Duration::ZERO)
+    }
+
+    #[test]
+    fn parses_answers() {
+        let day = summary(
+            RunStatus::Success,
+            &[
+                "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms @ 10 samples)",
+                "Part 2: ✖             ",
+            ],
+        );
+        assert_eq!(day.parts[0], PartStatus::Solved("42".into()));
+        assert_eq!(day.parts[1], PartStatus::Unsolved);
+        assert_eq!(day.stars(), 1);
+        assert!(day.is_failure());
+    }
+
+    #[test]
+    fn parses_multiline_answers() {
+        let day = summary(
+            RunStatus::Success,
+            &["Part 1: ▼  (1.0ms)", "#..#", "Part 2: \x1b[1mabc (def)\x1b[0m (2.0µs)"],
+        );
+        assert_eq!(day.parts[0], PartStatus::Solved("▼".into()));
+        assert_eq!(day.parts[1], PartStatus::Solved("abc (def)".into()));
+        assert!(!day.is_failure());
+    }
+
+    #[test]
+    fn reports_failures() {
+        let panicked = summary(RunStatus::Failed, &["Part 1: \x1b[1m1\x1b[0m"]);
+        assert_eq!(panicked.cell(1), "panicked");
+        assert!(panicked.is_failure());
+
+        let timed_out = summary(RunStatus::TimedOut, &[]);
+        assert_eq!(timed_out.cell(0), "timed out");
+
+        let missing = summary(RunStatus::NotScaffolded, &[]);
+        assert_eq!(missing.cell(0), "not scaffolded");
+        assert!(!missing.is_failure());
+    }
+}
@@ -0,0 +1,340 @@
+/// Renders frames fed by a solution, either as an animation in the terminal or as image files.
+///
+/// Solutions call [`frame`] whenever there is something to look at. The closure is only evaluated
+/// when `cargo solve` was invoked with `--visualize` or `--record <format>`, so leaving the calls in
+/// a solution costs next to nothing during normal runs and benchmarks.
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, stdout, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+use std::{env, process};
+
+use crate::grid::Grid;
+use crate::template::Day;
+
+/// A single pixel as red, green and blue components.
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const GREY: Rgb = [110, 110, 110];
+pub const RED: Rgb = [220, 50, 47];
+pub const GREEN: Rgb = [60, 180, 75];
+pub const YELLOW: Rgb = [255, 200, 0];
+pub const BLUE: Rgb = [38, 139, 210];
+
+const DEFAULT_FPS: u32 = 30;
+const DEFAULT_SCALE: usize = 4;
+
+/// File formats frames can be recorded to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// One binary PPM image per frame.
+    Ppm,
+    /// One PNG image per frame.
+    Png,
+    /// A single animated GIF.
+    Gif,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ppm" => Ok(Format::Ppm),
+            "png" => Ok(Format::Png),
+            "gif" => Ok(Format::Gif),
+            _ => Err(format!(
+                "unknown format `{s}`, expecting one of ppm, png, gif"
+            )),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+            Format::Gif => "gif",
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Options {
+    terminal: bool,
+    record: Option<Format>,
+    fps: u32,
+    scale: usize,
+}
+
+static OPTIONS: OnceLock<Option<Options>> = OnceLock::new();
+static FRAMES: Mutex<Vec<Grid<Rgb>>> = Mutex::new(Vec::new());
+
+fn arg_value<T: FromStr>(args: &[String], name: &str) -> Option<T> {
+    let index = args.iter().position(|x| x == name)?;
+    let Some(value) = args.get(index + 1).and_then(|x| x.parse().ok()) else {
+        eprintln!("Unexpected command-line input. Expected a valid value after `{name}`.");
+        process::exit(1);
+    };
+    Some(value)
+}
+
+fn options() -> Option<&'static Options> {
+    OPTIONS
+        .get_or_init(|| {
+            let args: Vec<String> = env::args().collect();
+            let terminal = args.iter().any(|x| x == "--visualize");
+            let record = arg_value(&args, "--record");
+
+            if !terminal && record.is_none() {
+                return None;
+            }
+
+            Some(Options {
+                terminal,
+                record,
+                fps: arg_value(&args, "--fps").unwrap_or(DEFAULT_FPS).max(1),
+                scale: arg_value(&args, "--scale").unwrap_or(DEFAULT_SCALE).max(1),
+            })
+        })
+        .as_ref()
+}
+
+/// Returns `true` if frames passed to [`frame`] are rendered or recorded.
+pub fn is_enabled() -> bool {
+    options().is_some()
+}
+
+/// Feeds a frame to the visualizer. `f` is only called when visualization is enabled.
+pub fn frame(f: impl FnOnce() -> Grid<Rgb>) {
+    let Some(options) = options() else {
+        return;
+    };
+
+    let grid = f();
+    let mut frames = FRAMES.lock().unwrap();
+
+    if options.terminal {
+        let mut stdout = stdout().lock();
+        let cursor = if frames.is_empty() {
+            "\x1b[2J\x1b[H"
+        } else {
+            "\x1b[H"
+        };
+        let _ = write!(stdout, "{cursor}{}", render_ansi(&grid));
+        let _ = stdout.flush();
+        thread::sleep(Duration::from_secs(1) / options.fps);
+    }
+
+    frames.push(grid);
+}
+
+/// Writes out the frames recorded for one part of a solution and resets the recorder.
+/// Called by the runner once a part has finished.
+pub fn finish(day: Day, part: u8) {
+    let Some(options) = options() else {
+        return;
+    };
+
+    let frames = std::mem::take(&mut *FRAMES.lock().unwrap());
+
+    if options.terminal && !frames.is_empty() {
+        println!();
+    }
+
+    let Some(format) = options.record else {
+        return;
+    };
+
+    if frames.is_empty() {
+        println!("No frames were recorded for part {part}.");
+        return;
+    }
+
+    let path = get_output_path(day, part, format);
+    match record(&frames, &path, format, options) {
+        Ok(()) => println!(
+            "🎄 Recorded {} frame(s) to \"{}\".",
+            frames.len(),
+            path.display()
+        ),
+        Err(e) => eprintln!("Failed to record frames: {e}"),
+    }
+}
+
+fn get_output_path(day: Day, part: u8, format: Format) -> PathBuf {
+    let dir = Path::new("data").join("visualizations");
+    match format {
+        Format::Gif => dir.join(format!("{day}-{part}.gif")),
+        Format::Ppm | Format::Png => dir.join(format!("{day}-{part}")),
+    }
+}
+
+fn record(frames: &[Grid<Rgb>], path: &Path, format: Format, options: &Options) -> io::Result<()> {
+    match format {
+        Format::Gif => {
+            fs::create_dir_all(path.parent().unwrap())?;
+            write_gif(frames, path, options)
+        }
+        Format::Ppm | Format::Png => {
+            fs::create_dir_all(path)?;
+            for (index, grid) in frames.iter().enumerate() {
+                let file = path.join(format!("frame-{index:05}.{format}"));
+                let mut writer = BufWriter::new(fs::File::create(file)?);
+                if format == Format::Ppm {
+                    write_ppm(&mut writer, grid, options.scale)?;
+                } else {
+                    write_png(&mut writer, grid, options.scale)?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders a frame for a 24-bit color terminal.
+/// Every character cell shows two pixels stacked on top of each other.
+pub fn render_ansi(grid: &Grid<Rgb>) -> String {
+    let mut out = String::new();
+    for y in (0..grid.height()).step_by(2) {
+        for x in 0..grid.width() {
+            let [r, g, b] = *grid.get((x, y)).unwrap();
+            match grid.get((x, y + 1)) {
+                Some([br, bg, bb]) => {
+                    out.push_str(&format!("\x1b[38;2;{r};{g};{b}m\x1b[48;2;{br};{bg};{bb}m▀"));
+                }
+                None => out.push_str(&format!("\x1b[38;2;{r};{g};{b}m\x1b[49m▀")),
+            }
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Scales a frame up to an RGB byte buffer where every cell becomes a `scale` x `scale` square.
+fn to_rgb_bytes(grid: &Grid<Rgb>, scale: usize) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(grid.width() * grid.height() * scale * scale * 3);
+    for row in grid.rows() {
+        for _ in 0..scale {
+            for pixel in row {
+                for _ in 0..scale {
+                    bytes.extend_from_slice(pixel);
+                }
+            }
+        }
+    }
+    bytes
+}
+
+/// Writes a frame as a binary PPM (P6) image.
+pub fn write_ppm(writer: &mut impl Write, grid: &Grid<Rgb>, scale: usize) -> io::Result<()> {
+    write!(
+        writer,
+        "P6\n{} {}\n255\n",
+        grid.width() * scale,
+        grid.height() * scale
+    )?;
+    writer.write_all(&to_rgb_bytes(grid, scale))
+}
+
+#[cfg(feature = "visualize")]
+fn write_png(writer: &mut impl Write, grid: &Grid<Rgb>, scale: usize) -> io::Result<()> {
+    #[allow(clippy::cast_possible_truncation)]
+    let mut encoder = png::Encoder::new(
+        writer,
+        (grid.width() * scale) as u32,
+        (grid.height() * scale) as u32,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer
+        .write_image_data(&to_rgb_bytes(grid, scale))
+        .map_err(io::Error::other)
+}
+
+#[cfg(feature = "visualize")]
+fn write_gif(frames: &[Grid<Rgb>], path: &Path, options: &Options) -> io::Result<()> {
+    let (width, height) = (frames[0].width(), frames[0].height());
+    let file = BufWriter::new(fs::File::create(path)?);
+    #[allow(clippy::cast_possible_truncation)]
+    let (gif_width, gif_height) = (
+        (width * options.scale) as u16,
+        (height * options.scale) as u16,
+    );
+    let mut encoder =
+        gif::Encoder::new(file, gif_width, gif_height, &[]).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    #[allow(clippy::cast_possible_truncation)]
+    let delay = (100 / options.fps).max(1) as u16;
+    for grid in frames {
+        if (grid.width(), grid.height()) != (width, height) {
+            return Err(io::Error::other(
+                "all frames of a GIF need to have the same size",
+            ));
+        }
+        let mut frame = gif::Frame::from_rgb_speed(
+            gif_width,
+            gif_height,
+            &to_rgb_bytes(grid, options.scale),
+            10,
+        );
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "visualize"))]
+fn write_png(_: &mut impl Write, _: &Grid<Rgb>, _: usize) -> io::Result<()> {
+    Err(io::Error::other(
+        "PNG output requires the `visualize` feature.",
+    ))
+}
+
+#[cfg(not(feature = "visualize"))]
+fn write_gif(_: &[Grid<Rgb>], _: &Path, _: &Options) -> io::Result<()> {
+    Err(io::Error::other(
+        "GIF output requires the `visualize` feature.",
+    ))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render_ansi, write_ppm, BLACK, WHITE};
+    use crate::grid::Grid;
+
+    #[test]
+    fn writes_scaled_ppm() {
+        let grid = Grid::from_points(2, 1, [(0, 0)], WHITE, BLACK);
+        let mut out = vec![];
+        write_ppm(&mut out, &grid, 2).unwrap();
+        let header = b"P6\n4 2\n255\n";
+        assert_eq!(&out[..header.len()], header);
+        assert_eq!(out.len(), header.len() + 4 * 2 * 3);
+        assert_eq!(&out[header.len()..header.len() + 6], &[255; 6]);
+        assert_eq!(&out[header.len() + 6..header.len() + 12], &[0; 6]);
+    }
+
+    #[test]
+    fn renders_two_rows_per_line() {
+        let grid = Grid::new(3, 3, WHITE);
+        let out = render_ansi(&grid);
+        assert_eq!(out.lines().count(), 2);
+        assert_eq!(out.matches('▀').count(), 6);
+    }
+}
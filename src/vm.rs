@@ -0,0 +1,337 @@
+/// Building blocks for the small register machines that show up in assembly-style puzzles.
+///
+/// A puzzle implements [`InstructionSet`] for its opcodes, and [`Vm`] takes care of fetching,
+/// tracing, cycle limits and collecting output.
+use std::fmt::Display;
+
+/// Errors raised while decoding or executing a program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VmError {
+    InvalidOpcode { ip: usize, opcode: i64 },
+    InvalidOperand { ip: usize, operand: i64 },
+    UnexpectedEnd { ip: usize },
+    CycleLimit(usize),
+}
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VmError::InvalidOpcode { ip, opcode } => {
+                write!(f, "invalid opcode `{opcode}` at {ip}.")
+            }
+            VmError::InvalidOperand { ip, operand } => {
+                write!(f, "invalid operand `{operand}` at {ip}.")
+            }
+            VmError::UnexpectedEnd { ip } => {
+                write!(f, "program ended in the middle of the instruction at {ip}.")
+            }
+            VmError::CycleLimit(cycles) => {
+                write!(f, "program did not halt within {cycles} cycles.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VmError {}
+
+/// What the machine should do after an instruction has been executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// Continue with the instruction right after this one.
+    Next,
+    /// Continue at the given address.
+    Jump(usize),
+    /// Stop the machine.
+    Halt,
+}
+
+/// The mutable state of a machine.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct State {
+    pub registers: Vec<i64>,
+    pub ip: usize,
+    pub output: Vec<i64>,
+    pub cycles: usize,
+}
+
+/// An instruction set that a [`Vm`] can run.
+pub trait InstructionSet: Sized + Display {
+    /// Register names, used for register dumps. The machine has one register per name.
+    const REGISTERS: &'static [&'static str];
+
+    /// Decodes the instruction at `ip`, returning it along with its length in words.
+    fn decode(program: &[i64], ip: usize) -> Result<(Self, usize), VmError>;
+
+    /// Executes the instruction, which was decoded at `state.ip`.
+    fn execute(&self, state: &mut State) -> Result<Control, VmError>;
+}
+
+/// Runs a program of an [`InstructionSet`].
+#[derive(Debug, Clone)]
+pub struct Vm<I: InstructionSet> {
+    pub program: Vec<i64>,
+    pub state: State,
+    _instructions: std::marker::PhantomData<I>,
+}
+
+impl<I: InstructionSet> Vm<I> {
+    pub fn new(program: Vec<i64>, registers: &[i64]) -> Self {
+        let mut state = State {
+            registers: vec![0; I::REGISTERS.len()],
+            ..State::default()
+        };
+        for (register, value) in state.registers.iter_mut().zip(registers) {
+            *register = *value;
+        }
+        Self {
+            program,
+            state,
+            _instructions: std::marker::PhantomData,
+        }
+    }
+
+    /// Resets the machine to the start of the program with new register values.
+    pub fn reset(&mut self, registers: &[i64]) {
+        *self = Self::new(std::mem::take(&mut self.program), registers);
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.state.ip >= self.program.len()
+    }
+
+    /// Executes a single instruction. Returns `false` once the machine has halted.
+    pub fn step(&mut self) -> Result<bool, VmError> {
+        if self.is_halted() {
+            return Ok(false);
+        }
+
+        let (instruction, len) = I::decode(&self.program, self.state.ip)?;
+        self.state.cycles += 1;
+        match instruction.execute(&mut self.state)? {
+            Control::Next => self.state.ip += len,
+            Control::Jump(ip) => self.state.ip = ip,
+            Control::Halt => {
+                self.state.ip = self.program.len();
+                return Ok(false);
+            }
+        }
+        Ok(!self.is_halted())
+    }
+
+    /// Runs until the machine halts, failing if that takes more than `limit` cycles.
+    pub fn run(&mut self, limit: Option<usize>) -> Result<&[i64], VmError> {
+        while self.step()? {
+            if limit.is_some_and(|limit| self.state.cycles >= limit) {
+                return Err(VmError::CycleLimit(self.state.cycles));
+            }
+        }
+        Ok(&self.state.output)
+    }
+
+    /// Like [`Vm::run`], but prints every instruction along with a register dump.
+    pub fn trace(&mut self, limit: Option<usize>) -> Result<&[i64], VmError> {
+        println!("{}", self.dump_registers());
+        while !self.is_halted() {
+            let (instruction, _) = I::decode(&self.program, self.state.ip)?;
+            let ip = self.state.ip;
+            let running = self.step()?;
+            println!(
+                "{ip:04}: {:<16} | {}",
+                instruction.to_string(),
+                self.dump_registers()
+            );
+            if !running {
+                break;
+            }
+            if limit.is_some_and(|limit| self.state.cycles >= limit) {
+                return Err(VmError::CycleLimit(self.state.cycles));
+            }
+        }
+        Ok(&self.state.output)
+    }
+
+    /// Formats every register as `NAME=value`.
+    pub fn dump_registers(&self) -> String {
+        I::REGISTERS
+            .iter()
+            .zip(&self.state.registers)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Prints the program symbolically, one instruction per line.
+    pub fn disassemble(&self) -> Result<String, VmError> {
+        disassemble::<I>(&self.program)
+    }
+}
+
+/// Prints a program symbolically, one instruction per line.
+pub fn disassemble<I: InstructionSet>(program: &[i64]) -> Result<String, VmError> {
+    let mut lines = vec![];
+    let mut ip = 0;
+    while ip < program.len() {
+        let (instruction, len) = I::decode(program, ip)?;
+        lines.push(format!("{ip:04}: {instruction}"));
+        ip += len;
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Solves for the input that makes a program print `target`.
+///
+/// Works for the common pattern of a program that loops over its input register, printing one
+/// value and shifting the register right by `bits` every iteration: the input is built up `bits`
+/// at a time, starting with the digit that produces the last output. `run` executes the program
+/// for a given input. Returns the smallest matching input.
+pub fn reverse_search(
+    target: &[i64],
+    bits: u32,
+    mut run: impl FnMut(u64) -> Vec<i64>,
+) -> Option<u64> {
+    fn search(
+        target: &[i64],
+        bits: u32,
+        prefix: u64,
+        matched: usize,
+        run: &mut impl FnMut(u64) -> Vec<i64>,
+    ) -> Option<u64> {
+        if matched == target.len() {
+            return Some(prefix);
+        }
+        for digit in 0..1u64 << bits {
+            let candidate = (prefix << bits) | digit;
+            if candidate == 0 {
+                continue;
+            }
+            let output = run(candidate);
+            if output.len() == matched + 1 && target.ends_with(&output) {
+                if let Some(input) = search(target, bits, candidate, matched + 1, run) {
+                    return Some(input);
+                }
+            }
+        }
+        None
+    }
+
+    search(target, bits, 0, 0, &mut run)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{disassemble, reverse_search, Control, InstructionSet, State, Vm, VmError};
+    use std::fmt::Display;
+
+    /// inc r | dec r | jnz r offset | out r
+    enum Toy {
+        Inc(usize),
+        Dec(usize),
+        Jnz(usize, i64),
+        Out(usize),
+    }
+
+    impl Display for Toy {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Toy::Inc(r) => write!(f, "inc {}", Toy::REGISTERS[*r]),
+                Toy::Dec(r) => write!(f, "dec {}", Toy::REGISTERS[*r]),
+                Toy::Jnz(r, offset) => write!(f, "jnz {} {offset}", Toy::REGISTERS[*r]),
+                Toy::Out(r) => write!(f, "out {}", Toy::REGISTERS[*r]),
+            }
+        }
+    }
+
+    impl InstructionSet for Toy {
+        const REGISTERS: &'static [&'static str] = &["a", "b"];
+
+        fn decode(program: &[i64], ip: usize) -> Result<(Self, usize), VmError> {
+            let operand = |offset: usize| {
+                program
+                    .get(ip + offset)
+                    .copied()
+                    .ok_or(VmError::UnexpectedEnd { ip })
+            };
+            let register = |offset: usize| {
+                let value = operand(offset)?;
+                usize::try_from(value)
+                    .ok()
+                    .filter(|r| *r < Self::REGISTERS.len())
+                    .ok_or(VmError::InvalidOperand { ip, operand: value })
+            };
+            match program[ip] {
+                0 => Ok((Toy::Inc(register(1)?), 2)),
+                1 => Ok((Toy::Dec(register(1)?), 2)),
+                2 => Ok((Toy::Jnz(register(1)?, operand(2)?), 3)),
+                3 => Ok((Toy::Out(register(1)?), 2)),
+                opcode => Err(VmError::InvalidOpcode { ip, opcode }),
+            }
+        }
+
+        fn execute(&self, state: &mut State) -> Result<Control, VmError> {
+            match self {
+                Toy::Inc(r) => state.registers[*r] += 1,
+                Toy::Dec(r) => state.registers[*r] -= 1,
+                Toy::Jnz(r, offset) => {
+                    if state.registers[*r] != 0 {
+                        return Ok(Control::Jump(
+                            state.ip.wrapping_add_signed(*offset as isize),
+                        ));
+                    }
+                }
+                Toy::Out(r) => state.output.push(state.registers[*r]),
+            }
+            Ok(Control::Next)
+        }
+    }
+
+    // out b; inc b; dec a; jnz a -6
+    const COUNTDOWN: [i64; 9] = [3, 1, 0, 1, 1, 0, 2, 0, -6];
+
+    #[test]
+    fn runs_program() {
+        let mut vm = Vm::<Toy>::new(COUNTDOWN.to_vec(), &[3]);
+        assert_eq!(vm.run(None).unwrap(), &[0, 1, 2]);
+        assert_eq!(vm.state.registers, vec![0, 3]);
+        assert_eq!(vm.dump_registers(), "a=0 b=3");
+        assert!(vm.is_halted());
+    }
+
+    #[test]
+    fn enforces_cycle_limit() {
+        let mut vm = Vm::<Toy>::new(COUNTDOWN.to_vec(), &[1000]);
+        assert_eq!(vm.run(Some(10)), Err(VmError::CycleLimit(10)));
+    }
+
+    #[test]
+    fn reports_invalid_opcodes() {
+        let mut vm = Vm::<Toy>::new(vec![0, 0, 9], &[]);
+        assert_eq!(
+            vm.run(None),
+            Err(VmError::InvalidOpcode { ip: 2, opcode: 9 })
+        );
+    }
+
+    #[test]
+    fn disassembles_program() {
+        assert_eq!(
+            disassemble::<Toy>(&COUNTDOWN).unwrap(),
+            "0000: out b\n0002: inc b\n0004: dec a\n0006: jnz a -6"
+        );
+    }
+
+    #[test]
+    fn reverse_searches_input() {
+        // prints the octal digits of the input, least significant first.
+        let run = |mut a: u64| {
+            let mut out = vec![];
+            while a != 0 {
+                out.push((a & 7) as i64);
+                a >>= 3;
+            }
+            out
+        };
+        assert_eq!(reverse_search(&[5, 3, 1], 3, run), Some(0o135));
+    }
+}